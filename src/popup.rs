@@ -16,15 +16,50 @@ use crate::{
     },
     core::{
         pool::Handle,
-        math::vec2::Vec2,
+        math::{
+            vec2::Vec2,
+            mat3::Mat3,
+        },
     },
     border::BorderBuilder,
+    brush::Brush,
     NodeHandleMapping,
 };
 use std::ops::{Deref, DerefMut};
 
 #[derive(Copy, Clone, PartialEq, Debug)]
-pub enum Placement {
+pub enum HAlign {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum VAlign {
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// Dismissal behaviour of a popup. `LightDismiss` closes on a click outside its bounds (a context
+/// menu), while `Modal` swallows all input beneath it and can only be closed programmatically (a
+/// dialog or message box).
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PopupMode {
+    LightDismiss,
+    Modal,
+}
+
+/// Visual effect played when a popup opens or closes. `Scale` grows the body from ~0.9 to 1.0
+/// anchored at the placement point, `Fade` interpolates its opacity, and `FadeScale` does both.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TransitionKind {
+    Fade,
+    Scale,
+    FadeScale,
+}
+
+pub enum Placement<M: 'static, C: 'static + Control<M, C>> {
     LeftTop,
     RightTop,
     Center,
@@ -32,13 +67,89 @@ pub enum Placement {
     RightBottom,
     Cursor,
     Position(Vec2),
+    /// Attaches the popup to another widget's `screen_bounds()`: `h_align`/`v_align` pick the
+    /// anchor, e.g. `Top + Left` drops the popup under the target (a dropdown), `Bottom` stacks
+    /// it above, `Center + Middle` centers it over the target.
+    RelativeTo {
+        target: Handle<UINode<M, C>>,
+        h_align: HAlign,
+        v_align: VAlign,
+    },
+}
+
+// `Placement` holds only `Copy` payloads (a `Handle` is `Copy`), but deriving would pull in
+// spurious `M: Copy`/`C: Copy` bounds, so the trait impls are written by hand like the rest of
+// the generic widget types in this crate.
+impl<M: 'static, C: 'static + Control<M, C>> Clone for Placement<M, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: 'static, C: 'static + Control<M, C>> Copy for Placement<M, C> {}
+
+impl<M: 'static, C: 'static + Control<M, C>> PartialEq for Placement<M, C> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Placement::LeftTop, Placement::LeftTop)
+            | (Placement::RightTop, Placement::RightTop)
+            | (Placement::Center, Placement::Center)
+            | (Placement::LeftBottom, Placement::LeftBottom)
+            | (Placement::RightBottom, Placement::RightBottom)
+            | (Placement::Cursor, Placement::Cursor) => true,
+            (Placement::Position(a), Placement::Position(b)) => a == b,
+            (
+                Placement::RelativeTo { target: t1, h_align: h1, v_align: v1 },
+                Placement::RelativeTo { target: t2, h_align: h2, v_align: v2 },
+            ) => t1 == t2 && h1 == h2 && v1 == v2,
+            _ => false,
+        }
+    }
+}
+
+impl<M: 'static, C: 'static + Control<M, C>> std::fmt::Debug for Placement<M, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Placement::LeftTop => write!(f, "LeftTop"),
+            Placement::RightTop => write!(f, "RightTop"),
+            Placement::Center => write!(f, "Center"),
+            Placement::LeftBottom => write!(f, "LeftBottom"),
+            Placement::RightBottom => write!(f, "RightBottom"),
+            Placement::Cursor => write!(f, "Cursor"),
+            Placement::Position(p) => write!(f, "Position({:?})", p),
+            Placement::RelativeTo { target, h_align, v_align } => f
+                .debug_struct("RelativeTo")
+                .field("target", target)
+                .field("h_align", h_align)
+                .field("v_align", v_align)
+                .finish(),
+        }
+    }
 }
 
 pub struct Popup<M: 'static, C: 'static + Control<M, C>> {
     widget: Widget<M, C>,
-    placement: Placement,
+    placement: Placement<M, C>,
     stays_open: bool,
     is_open: bool,
+    fit_to_screen: bool,
+    screen_size: Vec2,
+    pending_fit: Option<(Vec2, bool)>,
+    owner: Handle<UINode<M, C>>,
+    open_children: Vec<Handle<UINode<M, C>>>,
+    // Animation: `is_open` tracks the logical state while `anim_t` tracks the visual progress in
+    // `0..1` (0 fully hidden, 1 fully shown). The two can disagree mid-transition, which is what
+    // lets a repeated Open/Close reverse smoothly instead of snapping.
+    transition_duration: f32,
+    transition_kind: TransitionKind,
+    anim_t: f32,
+    anchor: Vec2,
+    // `true` while this popup owns a picking restriction. The restriction is released only once a
+    // close transition has fully played out (see `update`), so it can't be popped early while the
+    // body is still rendered during fade-out.
+    restriction_held: bool,
+    mode: PopupMode,
+    backdrop: Handle<UINode<M, C>>,
     content: Handle<UINode<M, C>>,
     body: Handle<UINode<M, C>>,
 }
@@ -64,6 +175,18 @@ impl<M, C: 'static + Control<M, C>> Control<M, C> for Popup<M, C> {
             placement: self.placement,
             stays_open: false,
             is_open: false,
+            fit_to_screen: self.fit_to_screen,
+            screen_size: self.screen_size,
+            pending_fit: None,
+            owner: self.owner,
+            open_children: Default::default(),
+            transition_duration: self.transition_duration,
+            transition_kind: self.transition_kind,
+            anim_t: 0.0,
+            anchor: Vec2::ZERO,
+            restriction_held: false,
+            mode: self.mode,
+            backdrop: self.backdrop,
             content: self.content,
             body: self.body,
         })
@@ -74,6 +197,22 @@ impl<M, C: 'static + Control<M, C>> Control<M, C> for Popup<M, C> {
             self.content = *content;
         }
         self.body = *node_map.get(&self.body).unwrap();
+        if let Some(backdrop) = node_map.get(&self.backdrop) {
+            self.backdrop = *backdrop;
+        }
+        if let Placement::RelativeTo { target, .. } = &mut self.placement {
+            if let Some(mapped) = node_map.get(target) {
+                *target = *mapped;
+            }
+        }
+        if let Some(owner) = node_map.get(&self.owner) {
+            self.owner = *owner;
+        }
+        for child in self.open_children.iter_mut() {
+            if let Some(mapped) = node_map.get(child) {
+                *child = *mapped;
+            }
+        }
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface<M, C>, message: &mut UiMessage<M, C>) {
@@ -83,63 +222,138 @@ impl<M, C: 'static + Control<M, C>> Control<M, C> for Popup<M, C> {
                     PopupMessage::Open => {
                         self.is_open = true;
                         self.set_visibility(true);
-                        if !self.stays_open {
+                        // A modal always grabs the restriction so it swallows input beneath it,
+                        // regardless of `stays_open`.
+                        if !self.stays_open || self.mode == PopupMode::Modal {
                             if ui.top_picking_restriction() != self.handle {
                                 ui.push_picking_restriction(self.handle);
                             }
+                            self.restriction_held = true;
                         }
                         self.send_message(UiMessage {
                             data: UiMessageData::Widget(WidgetMessage::TopMost),
                             destination: self.handle,
                             handled: false
                         });
-                        match self.placement {
-                            Placement::LeftTop => {
-                                self.set_desired_local_position(Vec2::ZERO);
-                            }
+                        self.screen_size = ui.screen_size();
+                        let desired = match self.placement {
+                            Placement::LeftTop => Vec2::ZERO,
                             Placement::RightTop => {
                                 let width = self.widget.actual_size().x;
                                 let screen_width = ui.screen_size().x;
-                                self.set_desired_local_position(
-                                    Vec2::new(screen_width - width, 0.0));
+                                Vec2::new(screen_width - width, 0.0)
                             }
                             Placement::Center => {
                                 let size = self.widget.actual_size();
                                 let screen_size = ui.screen_size;
-                                self.set_desired_local_position(
-                                    (screen_size - size).scale(0.5));
+                                (screen_size - size).scale(0.5)
                             }
                             Placement::LeftBottom => {
                                 let height = self.widget.actual_size().y;
                                 let screen_height = ui.screen_size().y;
-                                self.set_desired_local_position(
-                                    Vec2::new(0.0, screen_height - height));
+                                Vec2::new(0.0, screen_height - height)
                             }
                             Placement::RightBottom => {
                                 let size = self.widget.actual_size();
                                 let screen_size = ui.screen_size;
-                                self.set_desired_local_position(
-                                    screen_size - size);
+                                screen_size - size
                             }
-                            Placement::Cursor => {
-                                self.set_desired_local_position(
-                                    ui.cursor_position())
-                            }
-                            Placement::Position(position) => {
-                                self
-                                    .set_desired_local_position(
-                                        position)
+                            Placement::Cursor => ui.cursor_position(),
+                            Placement::Position(position) => position,
+                            Placement::RelativeTo { target, h_align, v_align } => {
+                                let size = self.widget.actual_size();
+                                // Without a target (`Handle::NONE`) the popup is centered on the
+                                // screen instead. A handle whose node has since been removed is
+                                // not detected here and is looked up as is.
+                                if target.is_some() {
+                                    let bounds = ui.node(target).screen_bounds();
+                                    let x = match h_align {
+                                        HAlign::Left => bounds.x,
+                                        HAlign::Center => bounds.x + (bounds.w - size.x) * 0.5,
+                                        HAlign::Right => bounds.x + bounds.w - size.x,
+                                    };
+                                    // `Top` drops the popup under the target (a dropdown),
+                                    // `Bottom` stacks it above, `Middle` centers it vertically
+                                    // over the target.
+                                    let y = match v_align {
+                                        VAlign::Top => bounds.y + bounds.h,
+                                        VAlign::Middle => bounds.y + (bounds.h - size.y) * 0.5,
+                                        VAlign::Bottom => bounds.y - size.y,
+                                    };
+                                    Vec2::new(x, y)
+                                } else {
+                                    (ui.screen_size - size).scale(0.5)
+                                }
                             }
+                        };
+                        self.set_desired_local_position(desired);
+                        // Grow/fade in from the invocation point. `desired` already equals that
+                        // point for cursor/position placement and is the body's corner otherwise.
+                        // Set before the clamp below, which re-anchors at the clamped position.
+                        self.anchor = desired;
+                        // `actual_size()` is usually stale right after `Open`, so remember the
+                        // chosen top-left and re-run the screen-fit clamp once layout is valid.
+                        if self.fit_to_screen {
+                            let flip = self.placement == Placement::Cursor;
+                            self.pending_fit = Some((desired, flip));
+                            self.fit_to_screen_now(desired, flip);
+                        }
+                        if self.transition_duration <= 0.0 {
+                            self.anim_t = 1.0;
+                            self.apply_transition();
+                        }
+                        // Stretch the dimming backdrop across the whole screen. It lives at the
+                        // screen origin (not under the popup root), so it stays put regardless of
+                        // where the body is placed or clamped.
+                        if self.backdrop.is_some() {
+                            let screen = ui.screen_size();
+                            let backdrop = ui.node_mut(self.backdrop);
+                            backdrop.set_desired_local_position(Vec2::ZERO);
+                            backdrop.set_width(screen.x);
+                            backdrop.set_height(screen.y);
+                            backdrop.set_visibility(true);
+                            // Keep the backdrop just beneath the popup body in the draw order.
+                            ui.send_message(UiMessage {
+                                data: UiMessageData::Widget(WidgetMessage::TopMost),
+                                destination: self.backdrop,
+                                handled: false,
+                            });
+                            ui.send_message(UiMessage {
+                                data: UiMessageData::Widget(WidgetMessage::TopMost),
+                                destination: self.handle,
+                                handled: false,
+                            });
                         }
                     }
                     PopupMessage::Close => {
                         self.is_open = false;
-                        self.set_visibility(false);
-                        if !self.stays_open {
-                            ui.pop_picking_restriction();
-                        }
-                        if ui.captured_node() == self.handle {
-                            ui.release_mouse_capture();
+                        // With a running close transition the teardown is deferred: the body keeps
+                        // rendering and the picking restriction stays held until `anim_t` reaches 0
+                        // in `update`, which re-sends `Close` to land back here with the animation
+                        // finished. Without a transition everything is torn down right away.
+                        if self.transition_duration > 0.0 && self.anim_t > 0.0 {
+                            // Deferred — see `update`.
+                        } else {
+                            self.anim_t = 0.0;
+                            self.set_visibility(false);
+                            if self.restriction_held {
+                                ui.pop_picking_restriction();
+                                self.restriction_held = false;
+                            }
+                            if ui.captured_node() == self.handle {
+                                ui.release_mouse_capture();
+                            }
+                            if self.backdrop.is_some() {
+                                ui.node_mut(self.backdrop).set_visibility(false);
+                            }
+                            // Closing a parent cascade-closes every submenu it opened.
+                            for child in std::mem::take(&mut self.open_children) {
+                                ui.send_message(UiMessage {
+                                    data: UiMessageData::Popup(PopupMessage::Close),
+                                    destination: child,
+                                    handled: false,
+                                });
+                            }
                         }
                     }
                     PopupMessage::Content(content) => {
@@ -149,10 +363,7 @@ impl<M, C: 'static + Control<M, C>> Control<M, C> for Popup<M, C> {
                         self.content = *content;
                         ui.link_nodes(self.content, self.body);
                     }
-                    &PopupMessage::Placement(placement) => {
-                        self.placement = placement;
-                        self.invalidate_layout();
-                    }
+                    _ => {}
                 }
             }
             _ => {}
@@ -161,10 +372,75 @@ impl<M, C: 'static + Control<M, C>> Control<M, C> for Popup<M, C> {
 
     fn handle_os_event(&mut self, self_handle: Handle<UINode<M, C>>, ui: &mut UserInterface<M, C>, event: &OsEvent) {
         if let OsEvent::MouseInput { state, .. } = event {
-            if *state == ButtonState::Pressed && ui.top_picking_restriction() == self_handle && self.is_open {
+            // A modal ignores outside-click dismissal entirely; it must be closed programmatically.
+            if *state == ButtonState::Pressed && ui.top_picking_restriction() == self_handle && self.is_open && !self.stays_open && self.mode == PopupMode::LightDismiss {
                 let pos = ui.cursor_position();
-                if !self.widget.screen_bounds().contains(pos.x, pos.y) && !self.stays_open {
+                // Walk the owner chain from this (top-most) popup upward, closing every popup whose
+                // own bounds don't contain the cursor and stopping as soon as an owner does (or is
+                // modal): a click inside a parent dismisses only the submenus stacked on top of it,
+                // and each `Close` cascades to that popup's own children.
+                //
+                // This node is taken out of the pool for the duration of `handle_os_event`, so its
+                // slot must be read through `self`/`self.owner` rather than `ui.node(self_handle)`;
+                // only the still-present ancestors are looked up through `ui.node`.
+                if !self.widget.screen_bounds().contains(pos.x, pos.y) {
                     self.close();
+                    let mut handle = self.owner;
+                    while handle.is_some() {
+                        if let UINode::Popup(popup) = ui.node(handle) {
+                            if popup.mode == PopupMode::Modal || popup.widget.screen_bounds().contains(pos.x, pos.y) {
+                                break;
+                            }
+                            let owner = popup.owner;
+                            ui.send_message(UiMessage {
+                                data: UiMessageData::Popup(PopupMessage::Close),
+                                destination: handle,
+                                handled: false,
+                            });
+                            handle = owner;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn update(&mut self, dt: f32) {
+        // The clamp queued in the `Open` handler runs against a stale `actual_size()`; redo it
+        // here once a layout pass has produced a valid size so the popup never spills off-screen.
+        if let Some((desired, flip)) = self.pending_fit {
+            if self.widget.actual_size().x > 0.0 && self.widget.actual_size().y > 0.0 {
+                self.fit_to_screen_now(desired, flip);
+                self.pending_fit = None;
+            }
+        }
+        // Drive the visual state toward the logical one. `is_open` picks the direction, so an
+        // Open arriving mid close-out (or vice versa) just reverses from the current `anim_t`.
+        if self.transition_duration > 0.0 {
+            let target = if self.is_open { 1.0 } else { 0.0 };
+            if self.anim_t != target {
+                let step = dt / self.transition_duration;
+                if self.anim_t < target {
+                    self.anim_t = (self.anim_t + step).min(target);
+                } else {
+                    self.anim_t = (self.anim_t - step).max(target);
+                }
+                self.apply_transition();
+                if !self.is_open && self.anim_t <= 0.0 {
+                    self.set_visibility(false);
+                    // The close animation is done. `update` has no `ui`, so route the deferred
+                    // teardown (pop the restriction, release capture, hide the backdrop,
+                    // cascade-close children) through a `Close` message, which lands in the branch
+                    // above with `anim_t` at 0. This must fire unconditionally: a `stays_open`
+                    // popup holds no picking restriction but can still own a mouse capture, a
+                    // backdrop and open children that need releasing.
+                    self.send_message(UiMessage {
+                        data: UiMessageData::Popup(PopupMessage::Close),
+                        destination: self.handle,
+                        handled: false,
+                    });
                 }
             }
         }
@@ -172,6 +448,54 @@ impl<M, C: 'static + Control<M, C>> Control<M, C> for Popup<M, C> {
 }
 
 impl<M, C: 'static + Control<M, C>> Popup<M, C> {
+    // Clamp (or, for cursor placement, flip) the popup so that the rectangle `desired .. desired +
+    // size` stays inside the screen. See `Placement` / the tiling-WM-style placement discipline.
+    fn fit_to_screen_now(&mut self, desired: Vec2, flip: bool) {
+        let size = self.widget.actual_size();
+        let screen = self.screen_size;
+        let mut p = desired;
+        if p.x + size.x > screen.x {
+            p.x = if flip { desired.x - size.x } else { screen.x - size.x };
+        }
+        if p.y + size.y > screen.y {
+            p.y = if flip { desired.y - size.y } else { screen.y - size.y };
+        }
+        p.x = p.x.max(0.0);
+        p.y = p.y.max(0.0);
+        if p != desired {
+            self.set_desired_local_position(p);
+            // The body now lives at the clamped `p`, so re-anchor the scale transition there —
+            // otherwise it would grow from the pre-clamp point, which may be off-screen.
+            self.anchor = p;
+        }
+    }
+
+    // Apply the current `anim_t` to the body as an eased opacity/scale. Scaling is anchored at the
+    // placement point so the popup appears to grow from where it was invoked.
+    fn apply_transition(&mut self) {
+        let e = Self::smoothstep(self.anim_t);
+        match self.transition_kind {
+            TransitionKind::Fade => self.widget.set_opacity(e),
+            TransitionKind::Scale => self.set_scale_about_anchor(0.9 + 0.1 * e),
+            TransitionKind::FadeScale => {
+                self.widget.set_opacity(e);
+                self.set_scale_about_anchor(0.9 + 0.1 * e);
+            }
+        }
+    }
+
+    fn set_scale_about_anchor(&mut self, scale: f32) {
+        let transform = Mat3::translate(self.anchor)
+            * Mat3::scale(Vec2::new(scale, scale))
+            * Mat3::translate(-self.anchor);
+        self.widget.set_render_transform(transform);
+    }
+
+    fn smoothstep(t: f32) -> f32 {
+        let t = t.max(0.0).min(1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
     pub fn open(&mut self) {
         if !self.is_open {
             self.invalidate_layout();
@@ -183,6 +507,20 @@ impl<M, C: 'static + Control<M, C>> Popup<M, C> {
         }
     }
 
+    /// Opens `child` as a submenu owned by this popup. The child must have been built with
+    /// `with_owner(this_handle)` so its outside-click dismissal walks back up to this popup;
+    /// closing this popup then cascade-closes `child`.
+    pub fn open_child(&mut self, child: Handle<UINode<M, C>>) {
+        if !self.open_children.contains(&child) {
+            self.open_children.push(child);
+        }
+        self.send_message(UiMessage {
+            data: UiMessageData::Popup(PopupMessage::Open),
+            destination: child,
+            handled: false,
+        });
+    }
+
     pub fn close(&mut self) {
         if self.is_open {
             self.invalidate_layout();
@@ -194,23 +532,26 @@ impl<M, C: 'static + Control<M, C>> Popup<M, C> {
         }
     }
 
-    pub fn set_placement(&mut self, placement: Placement) {
+    pub fn set_placement(&mut self, placement: Placement<M, C>) {
+        // `Placement` now carries a `Handle` (the `RelativeTo` target), so it can't ride through
+        // the non-generic `PopupMessage::Placement` variant anymore; apply it directly instead.
         if self.placement != placement {
             self.placement = placement;
             self.invalidate_layout();
-            self.send_message(UiMessage {
-                data: UiMessageData::Popup(PopupMessage::Placement(placement)),
-                destination: self.handle,
-                handled: false
-            });
         }
     }
 }
 
 pub struct PopupBuilder<M: 'static, C: 'static + Control<M, C>> {
     widget_builder: WidgetBuilder<M, C>,
-    placement: Placement,
+    placement: Placement<M, C>,
     stays_open: bool,
+    fit_to_screen: bool,
+    owner: Handle<UINode<M, C>>,
+    transition_duration: f32,
+    transition_kind: TransitionKind,
+    mode: PopupMode,
+    backdrop_brush: Option<Brush>,
     content: Handle<UINode<M, C>>,
 }
 
@@ -220,20 +561,62 @@ impl<M, C: 'static + Control<M, C>> PopupBuilder<M, C> {
             widget_builder,
             placement: Placement::Cursor,
             stays_open: false,
+            fit_to_screen: true,
+            owner: Handle::NONE,
+            transition_duration: 0.0,
+            transition_kind: TransitionKind::FadeScale,
+            mode: PopupMode::LightDismiss,
+            backdrop_brush: None,
             content: Default::default(),
         }
     }
 
-    pub fn with_placement(mut self, placement: Placement) -> Self {
+    pub fn with_placement(mut self, placement: Placement<M, C>) -> Self {
         self.placement = placement;
         self
     }
 
+    pub fn with_fit_to_screen(mut self, value: bool) -> Self {
+        self.fit_to_screen = value;
+        self
+    }
+
     pub fn stays_open(mut self, value: bool) -> Self {
         self.stays_open = value;
         self
     }
 
+    /// Links this popup to a parent popup so that it behaves as a submenu: outside-click
+    /// dismissal walks the owner chain instead of closing the parent, and closing the parent
+    /// cascade-closes this child.
+    pub fn with_owner(mut self, owner: Handle<UINode<M, C>>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Plays a fade/scale transition of the given `duration` (in seconds) whenever the popup
+    /// opens or closes. A non-positive duration (the default) keeps the instant show/hide.
+    pub fn with_transition(mut self, duration: f32, kind: TransitionKind) -> Self {
+        self.transition_duration = duration;
+        self.transition_kind = kind;
+        self
+    }
+
+    /// Switches between the default light-dismiss behaviour and a `Modal` one that blocks all
+    /// input beneath the popup and cannot be dismissed by an outside click.
+    pub fn with_modal(mut self, modal: bool) -> Self {
+        self.mode = if modal { PopupMode::Modal } else { PopupMode::LightDismiss };
+        self
+    }
+
+    /// Draws a full-screen backdrop painted with `brush` behind the body. It sizes itself to
+    /// `ui.screen_size()` and blocks clicks to everything underneath, which pairs naturally with
+    /// [`with_modal`](Self::with_modal) for dialogs.
+    pub fn with_backdrop(mut self, brush: Brush) -> Self {
+        self.backdrop_brush = Some(brush);
+        self
+    }
+
     pub fn with_content(mut self, content: Handle<UINode<M, C>>) -> Self {
         self.content = content;
         self
@@ -244,6 +627,18 @@ impl<M, C: 'static + Control<M, C>> PopupBuilder<M, C> {
             .with_child(self.content))
             .build(ui);
 
+        // The backdrop is a screen-space overlay kept *outside* the popup root's subtree so the
+        // fit-to-screen clamp (which can move the root) never drags it off the screen origin. It
+        // is sized, positioned and shown in the `Open` handler and re-stacked just below the body.
+        let backdrop = if let Some(brush) = self.backdrop_brush {
+            BorderBuilder::new(WidgetBuilder::new()
+                .with_background(brush)
+                .with_visibility(false))
+                .build(ui)
+        } else {
+            Handle::NONE
+        };
+
         let popup = Popup {
             widget: self.widget_builder
                 .with_child(body)
@@ -252,6 +647,18 @@ impl<M, C: 'static + Control<M, C>> PopupBuilder<M, C> {
             placement: self.placement,
             stays_open: self.stays_open,
             is_open: false,
+            fit_to_screen: self.fit_to_screen,
+            screen_size: Vec2::ZERO,
+            pending_fit: None,
+            owner: self.owner,
+            open_children: Default::default(),
+            transition_duration: self.transition_duration,
+            transition_kind: self.transition_kind,
+            anim_t: 0.0,
+            anchor: Vec2::ZERO,
+            restriction_held: false,
+            mode: self.mode,
+            backdrop,
             content: self.content,
             body,
         };